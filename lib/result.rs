@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+/// Convenience alias for results returned throughout the aftman library.
+pub type AftmanResult<T> = Result<T, AftmanError>;
+
+/// The error type returned by fallible aftman operations.
+#[derive(Debug, Error)]
+pub enum AftmanError {
+    #[error("file not found at path {0:?}")]
+    FileNotFound(PathBuf),
+
+    #[error(
+        "GitHub API rate limit reached{}",
+        .reset.as_ref().map(|r| format!("; resets at {r}")).unwrap_or_default()
+    )]
+    RateLimited { reset: Option<String> },
+
+    #[error("no checksum was published for asset {0:?}")]
+    ChecksumMissing(String),
+
+    #[error("checksum mismatch for {asset:?}: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        asset: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    #[error(transparent)]
+    InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
+}
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION, USER_AGENT},
+    Client, Request, Response, StatusCode,
+};
+use tracing::{info, warn};
+
+use crate::result::{AftmanError, AftmanResult};
+
+/// The environment variable an optional GitHub token is read from, used for
+/// higher rate limits and access to private-repo assets.
+const TOKEN_VAR: &str = "GITHUB_TOKEN";
+
+/**
+    Builds the shared reqwest [`Client`] used for all GitHub requests.
+
+    The client advertises a descriptive `User-Agent` (GitHub rejects requests
+    without one) and, when `GITHUB_TOKEN` is set in the environment, sends it
+    as a bearer token so listing and downloads get the authenticated rate
+    limit and can reach private repositories.
+*/
+pub fn new_client() -> AftmanResult<Client> {
+    let user_agent = format!(
+        "aftman/{} ({}; {})",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_str(&user_agent)?);
+
+    if let Ok(token) = std::env::var(TOKEN_VAR) {
+        if !token.is_empty() {
+            let mut value = HeaderValue::from_str(&format!("Bearer {token}"))?;
+            value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, value);
+        }
+    }
+
+    Ok(Client::builder().default_headers(headers).build()?)
+}
+
+/**
+    Sends a request, transparently handling GitHub's rate-limit responses.
+
+    On a `403`/`429` whose `x-ratelimit-remaining` is `0`, the reset epoch is
+    read from `x-ratelimit-reset`. If the wait is short we sleep until the
+    limit resets (logging that we are doing so) and retry once; otherwise we
+    return a clear [`AftmanError::RateLimited`] naming the reset time rather
+    than a generic HTTP failure.
+*/
+pub async fn send(client: &Client, request: Request) -> AftmanResult<Response> {
+    // Clone up front so we can retry the same request after waiting.
+    let retry = request.try_clone();
+    let response = client.execute(request).await?;
+
+    if !is_rate_limited(&response) {
+        return Ok(response);
+    }
+
+    let reset = rate_limit_reset(&response);
+    let wait = reset.map(seconds_until).unwrap_or(0);
+
+    // Only sleep for waits short enough to be reasonable; anything longer is
+    // surfaced to the user so they are not left staring at a hung process.
+    const MAX_WAIT: u64 = 60;
+    match retry {
+        Some(retry) if wait > 0 && wait <= MAX_WAIT => {
+            info!("GitHub rate limit hit, waiting {wait}s for it to reset");
+            tokio::time::sleep(Duration::from_secs(wait)).await;
+            Ok(client.execute(retry).await?)
+        }
+        _ => {
+            warn!("GitHub rate limit hit with no short reset window");
+            Err(AftmanError::RateLimited {
+                reset: reset.map(format_reset),
+            })
+        }
+    }
+}
+
+fn is_rate_limited(response: &Response) -> bool {
+    let status = response.status();
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return false;
+    }
+    response
+        .headers()
+        .get("x-ratelimit-remaining")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim() == "0")
+        .unwrap_or(false)
+}
+
+fn rate_limit_reset(response: &Response) -> Option<u64> {
+    response
+        .headers()
+        .get("x-ratelimit-reset")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+}
+
+/// Seconds from now until the given epoch, clamped at zero for times already
+/// in the past.
+fn seconds_until(reset_epoch: u64) -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    reset_epoch.saturating_sub(now)
+}
+
+/// Formats an epoch reset time as `HH:MM` in UTC for the user-facing error.
+fn format_reset(reset_epoch: u64) -> String {
+    let secs_of_day = reset_epoch % 86_400;
+    let hours = secs_of_day / 3_600;
+    let minutes = (secs_of_day % 3_600) / 60;
+    format!("{hours:02}:{minutes:02} UTC")
+}
@@ -0,0 +1,10 @@
+pub mod client;
+pub mod models;
+pub mod pipeline;
+
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+
+pub use self::client::new_client;
+pub use self::models::{Asset, Release};
+pub use self::pipeline::{Pipeline, PipelineContext, PipelineError, Step};
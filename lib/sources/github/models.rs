@@ -1,6 +1,16 @@
+use std::io::IsTerminal;
+use std::path::Path;
+
+use futures::StreamExt;
+use indicatif::{ProgressBar, ProgressStyle};
+use reqwest::{header::CONTENT_LENGTH, Client};
 use serde::Deserialize;
+use tokio::fs::File;
+use tokio::io::AsyncWriteExt;
 use url::Url;
 
+use crate::result::AftmanResult;
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Release {
     pub assets: Vec<Asset>,
@@ -8,9 +18,112 @@ pub struct Release {
     pub prerelease: bool,
 }
 
+impl Release {
+    /// Returns the asset that matches the given platform, identified by the
+    /// presence of both the OS and architecture fragments in its name - the
+    /// same selection the install flow uses to pick a download.
+    pub fn asset_for_platform(&self, os: &str, arch: &str) -> Option<&Asset> {
+        let os = os.to_ascii_lowercase();
+        let arch = arch.to_ascii_lowercase();
+        self.assets.iter().find(|asset| {
+            let name = asset.name.to_ascii_lowercase();
+            name.contains(&os) && name.contains(&arch)
+        })
+    }
+}
+
+/// Returns the newest stable release from a listing, skipping prereleases.
+///
+/// GitHub returns releases newest-first, so the first non-prerelease entry is
+/// the latest stable one.
+pub fn latest_stable(releases: &[Release]) -> Option<&Release> {
+    releases.iter().find(|release| !release.prerelease)
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Asset {
     pub id: u64,
     pub url: Url,
     pub name: String,
 }
+
+impl Asset {
+    /**
+        Downloads this asset to the given path, streaming the response body
+        straight to disk instead of buffering the whole thing in memory.
+
+        When stdout is a TTY, a progress bar sized from the `Content-Length`
+        header is rendered as the download proceeds; if the server omits the
+        length an indeterminate spinner is shown instead. In CI or when piped,
+        no progress is drawn so the output stays clean.
+    */
+    pub async fn download_to(
+        &self,
+        client: &Client,
+        path: impl AsRef<Path>,
+    ) -> AftmanResult<()> {
+        // Route through `client::send` so downloads share the same rate-limit
+        // and retry handling as the release listing does.
+        let request = client.get(self.url.clone()).build()?;
+        let response = super::client::send(client, request)
+            .await?
+            .error_for_status()?;
+
+        let total = response
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+
+        let progress = new_progress_bar(&self.name, total);
+
+        let mut file = File::create(path.as_ref()).await?;
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk).await?;
+            if let Some(progress) = &progress {
+                progress.inc(chunk.len() as u64);
+            }
+        }
+        file.flush().await?;
+
+        if let Some(progress) = progress {
+            progress.finish_and_clear();
+        }
+
+        Ok(())
+    }
+}
+
+/// Builds a progress bar for downloading `name`, or `None` when stdout is not
+/// a TTY. A known `total` gives a sized bar with throughput; an unknown size
+/// falls back to an indeterminate spinner.
+fn new_progress_bar(name: &str, total: Option<u64>) -> Option<ProgressBar> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+
+    let progress = match total {
+        Some(total) => {
+            let bar = ProgressBar::new(total);
+            bar.set_style(
+                ProgressStyle::with_template(
+                    "{msg} [{bar:30}] {bytes}/{total_bytes} ({bytes_per_sec})",
+                )
+                .unwrap()
+                .progress_chars("=> "),
+            );
+            bar
+        }
+        None => {
+            let spinner = ProgressBar::new_spinner();
+            spinner.set_style(
+                ProgressStyle::with_template("{msg} {spinner} {bytes} ({bytes_per_sec})").unwrap(),
+            );
+            spinner
+        }
+    };
+    progress.set_message(format!("Downloading {name}"));
+    Some(progress)
+}
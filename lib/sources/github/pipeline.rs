@@ -0,0 +1,400 @@
+use std::path::{Path, PathBuf};
+
+use reqwest::Client;
+use sha2::{Digest, Sha256};
+use tokio::fs::read;
+use tokio::task::spawn_blocking;
+use tracing::debug;
+
+use crate::result::{AftmanError, AftmanResult};
+
+use super::models::{Asset, Release};
+
+/**
+    Shared state threaded through every [`Step`] of an install [`Pipeline`].
+
+    Earlier steps populate the later fields (the resolved release, the
+    downloaded artifact path, ...) so that each step only has to read what the
+    ones before it produced.
+*/
+#[derive(Debug)]
+pub struct PipelineContext {
+    pub client: Client,
+    /// Directory that downloaded and extracted artifacts are staged in.
+    pub staging_dir: PathBuf,
+    /// The release resolved by [`Step::ResolveRelease`].
+    pub release: Option<Release>,
+    /// The asset chosen for the current platform.
+    pub asset: Option<Asset>,
+    /// Path of the downloaded asset on disk.
+    pub download_path: Option<PathBuf>,
+    /// Path of the extracted tool binary, ready to be written executable.
+    pub artifact_path: Option<PathBuf>,
+    /// Path the tool binary was written to by [`Step::WriteExecutable`].
+    pub installed_path: Option<PathBuf>,
+}
+
+impl PipelineContext {
+    pub fn new(client: Client, staging_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            client,
+            staging_dir: staging_dir.into(),
+            release: None,
+            asset: None,
+            download_path: None,
+            artifact_path: None,
+            installed_path: None,
+        }
+    }
+}
+
+/**
+    A single ordered stage of the install flow.
+
+    Each variant owns just the inputs it needs from the caller; everything
+    else is read from and written back to the shared [`PipelineContext`]. This
+    keeps the steps individually constructible and testable in isolation.
+*/
+#[derive(Debug)]
+pub enum Step {
+    /// Selects the asset matching the given platform out of the release and
+    /// records both on the context for the later steps to use.
+    ResolveRelease {
+        release: Release,
+        os: String,
+        arch: String,
+    },
+    /// Streams the chosen asset to the staging directory.
+    DownloadAsset,
+    /// Validates the downloaded asset against a published checksum asset,
+    /// aborting the pipeline on mismatch. A no-op when no checksum is found.
+    VerifyChecksum,
+    /// Extracts the downloaded archive, exposing the inner tool binary.
+    Extract,
+    /// Writes the extracted binary into tool storage with executable bits.
+    WriteExecutable { destination: PathBuf },
+    /// Recreates the alias links that point at the installed binary.
+    LinkAliases { aliases: Vec<String> },
+}
+
+impl Step {
+    /// A stable, human-readable name used in progress and error reporting.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ResolveRelease { .. } => "resolve release",
+            Self::DownloadAsset => "download asset",
+            Self::VerifyChecksum => "verify checksum",
+            Self::Extract => "extract",
+            Self::WriteExecutable { .. } => "write executable",
+            Self::LinkAliases { .. } => "link aliases",
+        }
+    }
+
+    async fn run(&self, cx: &mut PipelineContext) -> AftmanResult<()> {
+        match self {
+            Self::ResolveRelease { release, os, arch } => {
+                let asset = release.asset_for_platform(os, arch).ok_or_else(|| {
+                    AftmanError::from(std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("no asset in {} matches {os}-{arch}", release.tag_name),
+                    ))
+                })?;
+                cx.asset = Some(asset.clone());
+                cx.release = Some(release.clone());
+                Ok(())
+            }
+            Self::DownloadAsset => {
+                let asset = cx
+                    .asset
+                    .as_ref()
+                    .ok_or_else(|| missing_input("download requires a resolved asset"))?;
+                let path = cx.staging_dir.join(&asset.name);
+                asset.download_to(&cx.client, &path).await?;
+                cx.download_path = Some(path);
+                Ok(())
+            }
+            Self::VerifyChecksum => self.verify_checksum(cx).await,
+            Self::Extract => {
+                let download = cx
+                    .download_path
+                    .as_ref()
+                    .ok_or_else(|| missing_input("extract requires a downloaded asset"))?;
+                let artifact = extract_artifact(download, &cx.staging_dir).await?;
+                cx.artifact_path = Some(artifact);
+                Ok(())
+            }
+            Self::WriteExecutable { destination } => {
+                let artifact = cx
+                    .artifact_path
+                    .as_ref()
+                    .ok_or_else(|| missing_input("write executable requires an extracted artifact"))?;
+                let contents = read(artifact).await?;
+                crate::util::write_executable_file(destination, contents).await?;
+                cx.installed_path = Some(destination.clone());
+                Ok(())
+            }
+            Self::LinkAliases { aliases } => {
+                let installed = cx
+                    .installed_path
+                    .as_ref()
+                    .ok_or_else(|| missing_input("link aliases requires a written executable"))?;
+                // Aliases live next to the installed binary and dispatch to it
+                // the same way the primary link does.
+                let dir = installed
+                    .parent()
+                    .ok_or_else(|| missing_input("installed path has no parent directory"))?;
+                for alias in aliases {
+                    crate::util::write_executable_link(dir.join(alias), installed).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Downloads the release's `*.sha256` / checksums asset, if present, and
+    /// verifies the *downloaded* asset against it before it is extracted and
+    /// written executable into tool storage.
+    ///
+    /// This step runs after [`Step::DownloadAsset`] and before [`Step::Extract`],
+    /// so it validates `download_path` - `artifact_path` is only populated by
+    /// the later extract step and would be absent here. The inputs it needs
+    /// are produced by earlier steps, so their absence is a hard error rather
+    /// than a silent success: skipping verification is only appropriate when
+    /// the release genuinely publishes no checksum.
+    async fn verify_checksum(&self, cx: &mut PipelineContext) -> AftmanResult<()> {
+        let (Some(release), Some(asset), Some(download)) =
+            (&cx.release, &cx.asset, &cx.download_path)
+        else {
+            return Err(missing_input(
+                "verify checksum requires a resolved release and a downloaded asset",
+            ));
+        };
+
+        let Some(checksum_asset) = find_checksum_asset(release, &asset.name) else {
+            debug!("no checksum asset published for {}, skipping", asset.name);
+            return Ok(());
+        };
+
+        let checksum_path = cx.staging_dir.join(&checksum_asset.name);
+        checksum_asset
+            .download_to(&cx.client, &checksum_path)
+            .await?;
+        let expected = parse_checksum(&read(&checksum_path).await?, &asset.name)
+            .ok_or_else(|| AftmanError::ChecksumMissing(asset.name.clone()))?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(&read(download).await?);
+        let actual = hex_encode(&hasher.finalize());
+
+        if actual != expected {
+            return Err(AftmanError::ChecksumMismatch {
+                asset: asset.name.clone(),
+                expected,
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/**
+    An ordered list of [`Step`]s that make up a single tool install.
+
+    Running the pipeline executes each step in sequence against a shared
+    [`PipelineContext`]; if a step fails, the error records which step failed
+    so the caller sees `install failed during "verify checksum": ...` rather
+    than an opaque message.
+*/
+#[derive(Debug)]
+pub struct Pipeline {
+    steps: Vec<Step>,
+}
+
+impl Pipeline {
+    pub fn new(steps: Vec<Step>) -> Self {
+        Self { steps }
+    }
+
+    pub async fn run(self, cx: &mut PipelineContext) -> Result<(), PipelineError> {
+        for (index, step) in self.steps.iter().enumerate() {
+            let name = step.name();
+            debug!(step = name, index, "running install step");
+            step.run(cx)
+                .await
+                .map_err(|source| PipelineError { step: name, source })?;
+        }
+        Ok(())
+    }
+}
+
+/// An error from a single [`Step`], annotated with the step it came from.
+#[derive(Debug, thiserror::Error)]
+#[error("install failed during {step:?}")]
+pub struct PipelineError {
+    pub step: &'static str,
+    #[source]
+    pub source: AftmanError,
+}
+
+/// Builds the error returned when a step is reached without the context a
+/// preceding step was supposed to populate. This should only happen if the
+/// pipeline is assembled with its steps out of order.
+fn missing_input(message: &'static str) -> AftmanError {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message).into()
+}
+
+/// Extracts the tool binary out of a downloaded asset, staging it alongside
+/// the download. Zip archives (the format aftman tools publish) are unpacked
+/// and the contained executable is returned; a bare binary is used as-is.
+async fn extract_artifact(download: &Path, staging_dir: &Path) -> AftmanResult<PathBuf> {
+    let extension = download
+        .extension()
+        .map(|ext| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    // A bare binary (no archive extension) is already the artifact. Asset names
+    // often carry dotted versions (`tool-1.0.0-linux`), so anything that is not
+    // a known archive extension counts as a bare binary.
+    const ARCHIVES: &[&str] = &["zip", "tar", "gz", "tgz", "xz", "bz2", "zst", "7z", "rar"];
+    if !ARCHIVES.iter().any(|archive| extension.eq_ignore_ascii_case(archive)) {
+        return Ok(download.to_path_buf());
+    }
+    // Reject archive formats we do not know how to unpack rather than writing
+    // the compressed bytes out as a "binary" and producing a corrupt install.
+    if !extension.eq_ignore_ascii_case("zip") {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("unsupported asset archive format: {download:?}"),
+        )
+        .into());
+    }
+
+    let download = download.to_path_buf();
+    let staging_dir = staging_dir.to_path_buf();
+    // `zip` is a synchronous reader, so unpack on a blocking thread rather
+    // than stalling the async runtime on what can be a large archive.
+    spawn_blocking(move || unzip_single_binary(&download, &staging_dir))
+        .await
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?
+}
+
+/// Unpacks `archive` into `staging_dir` and returns the path of the extracted
+/// executable. Aftman release archives wrap a single tool binary alongside the
+/// occasional `LICENSE`/`README`, so the binary-looking entry is preferred
+/// over those rather than blindly taking the first file.
+fn unzip_single_binary(archive: &Path, staging_dir: &Path) -> AftmanResult<PathBuf> {
+    use std::io::copy;
+
+    let file = std::fs::File::open(archive)?;
+    let mut zip = zip::ZipArchive::new(file)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+    // Gather the file entries, then pick the one most likely to be the tool
+    // binary: prefer an entry with no extension or a `.exe` extension, and
+    // skip the documentation files that sometimes ride along.
+    let mut candidates = Vec::new();
+    for index in 0..zip.len() {
+        let entry = zip
+            .by_index(index)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        if entry.is_file() && !looks_like_metadata(entry.name()) {
+            candidates.push(index);
+        }
+    }
+    let chosen = candidates
+        .iter()
+        .copied()
+        .find(|index| {
+            let name = zip.by_index(*index).map(|e| e.name().to_owned()).unwrap_or_default();
+            matches!(
+                Path::new(&name).extension().and_then(|e| e.to_str()),
+                None | Some("exe")
+            )
+        })
+        .or_else(|| candidates.first().copied())
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("archive {archive:?} contained no files to extract"),
+            )
+        })?;
+
+    let mut entry = zip
+        .by_index(chosen)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let name = Path::new(entry.name())
+        .file_name()
+        .map(|n| n.to_owned())
+        .unwrap_or_else(|| entry.name().into());
+    let out_path = staging_dir.join(name);
+    let mut out = std::fs::File::create(&out_path)?;
+    copy(&mut entry, &mut out)?;
+    Ok(out_path)
+}
+
+/// Whether an archive entry is a documentation/metadata file rather than the
+/// tool binary, so extraction can skip it.
+fn looks_like_metadata(name: &str) -> bool {
+    let file = name.rsplit('/').next().unwrap_or(name).to_ascii_lowercase();
+    let stem = file.split('.').next().unwrap_or(&file);
+    matches!(stem, "license" | "licence" | "readme" | "changelog" | "notice")
+        || file.ends_with(".md")
+        || file.ends_with(".txt")
+        || file.ends_with(".sha256")
+}
+
+/// Finds the checksum asset that covers `binary_name`, matching either a
+/// per-binary `<name>.sha256` asset or a combined `checksums`/`SHA256SUMS`
+/// manifest.
+fn find_checksum_asset<'a>(release: &'a Release, binary_name: &str) -> Option<&'a Asset> {
+    let per_binary = format!("{binary_name}.sha256").to_ascii_lowercase();
+    // Match the per-binary file exactly, or a manifest under one of the
+    // canonical names - a substring match would latch onto unrelated assets
+    // like `checksums-docs.txt`.
+    const MANIFESTS: &[&str] = &[
+        "checksums",
+        "checksums.txt",
+        "sha256sums",
+        "sha256sums.txt",
+    ];
+    release.assets.iter().find(|asset| {
+        let name = asset.name.to_ascii_lowercase();
+        name == per_binary || MANIFESTS.contains(&name.as_str())
+    })
+}
+
+/// Parses the checksum for `binary_name` out of a checksum file, supporting
+/// both a bare single-hash file and the `<hash>␣␣<name>` manifest layout.
+fn parse_checksum(contents: &[u8], binary_name: &str) -> Option<String> {
+    let text = String::from_utf8_lossy(contents);
+    let trimmed = text.trim();
+
+    // A per-binary `.sha256` file is typically just the hash on its own.
+    if !trimmed.contains(char::is_whitespace) && !trimmed.is_empty() {
+        return Some(trimmed.to_ascii_lowercase());
+    }
+
+    for line in text.lines() {
+        let mut parts = line.split_whitespace();
+        // Skip blank lines rather than bailing on the whole manifest.
+        let Some(hash) = parts.next() else {
+            continue;
+        };
+        // The name may be prefixed with `*` for binary mode, per coreutils.
+        let name = parts.next().map(|n| n.trim_start_matches('*'));
+        if name == Some(binary_name) {
+            return Some(hash.to_ascii_lowercase());
+        }
+    }
+    None
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(out, "{byte:02x}");
+    }
+    out
+}
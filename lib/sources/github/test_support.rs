@@ -0,0 +1,150 @@
+//! Test support for driving [`super`] end to end without hitting real GitHub.
+//!
+//! [`MockGitHub`] spins up a throwaway HTTP server on an ephemeral port that
+//! serves canned `releases` JSON and fake asset bytes, and [`TempHome`] roots
+//! a disposable Aftman home in a temp directory. Together they let tests
+//! exercise the source, install, and link-recreation paths against
+//! deterministic fixtures.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+use super::models::Release;
+
+/// A minimal local stand-in for the GitHub REST API.
+///
+/// Routes are matched on path: `.../releases` returns the configured release
+/// listing, `/download/<name>` returns that asset's bytes, and everything else
+/// responds `404` so the source's not-found handling can be tested too.
+pub struct MockGitHub {
+    addr: SocketAddr,
+    /// Handle to the accept loop, aborted when the `MockGitHub` is dropped so
+    /// the server really does stop when the test is done with it.
+    server: JoinHandle<()>,
+}
+
+impl Drop for MockGitHub {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// The canned state a [`MockGitHub`] serves.
+#[derive(Default)]
+pub struct MockData {
+    /// Raw JSON returned for the releases listing.
+    pub releases_json: String,
+    /// Asset name -> bytes served from `/download/<name>`.
+    pub assets: HashMap<String, Vec<u8>>,
+}
+
+impl MockGitHub {
+    /// Starts a server serving `data` and returns once it is accepting
+    /// connections. The server is torn down when the returned value is
+    /// dropped, which aborts the accept loop and closes the listener.
+    pub async fn start(data: MockData) -> Self {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("failed to bind mock server");
+        let addr = listener.local_addr().expect("no local addr");
+
+        let data = Arc::new(data);
+        let server = tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    break;
+                };
+                let data = Arc::clone(&data);
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let Ok(n) = stream.read(&mut buf).await else {
+                        return;
+                    };
+                    let request = String::from_utf8_lossy(&buf[..n]);
+                    let path = request
+                        .lines()
+                        .next()
+                        .and_then(|line| line.split_whitespace().nth(1))
+                        .unwrap_or("/");
+                    let response = route(&data, path);
+                    let _ = stream.write_all(&response).await;
+                });
+            }
+        });
+
+        Self { addr, server }
+    }
+
+    /// The base URL the mock is listening on, e.g. `http://127.0.0.1:52314`.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+}
+
+fn route(data: &MockData, path: &str) -> Vec<u8> {
+    if path.ends_with("/releases") || path.contains("/releases?") {
+        return http_response(200, "application/json", data.releases_json.as_bytes());
+    }
+    if let Some(name) = path.strip_prefix("/download/") {
+        if let Some(bytes) = data.assets.get(name) {
+            return http_response(200, "application/octet-stream", bytes);
+        }
+    }
+    http_response(404, "application/json", br#"{"message":"Not Found"}"#)
+}
+
+fn http_response(status: u16, content_type: &str, body: &[u8]) -> Vec<u8> {
+    let reason = match status {
+        200 => "OK",
+        404 => "Not Found",
+        _ => "Unknown",
+    };
+    let mut response = format!(
+        "HTTP/1.1 {status} {reason}\r\n\
+         Content-Type: {content_type}\r\n\
+         Content-Length: {}\r\n\
+         \r\n",
+        body.len(),
+    )
+    .into_bytes();
+    response.extend_from_slice(body);
+    response
+}
+
+/// A disposable Aftman home rooted in a temp directory, cleaned up on drop.
+pub struct TempHome {
+    dir: TempDir,
+}
+
+impl TempHome {
+    /// Creates a fresh, empty home directory.
+    pub fn new() -> Self {
+        Self {
+            dir: TempDir::new().expect("failed to create temp home"),
+        }
+    }
+
+    /// The path that should be passed to `Home::load_from_path`.
+    pub fn path(&self) -> &std::path::Path {
+        self.dir.path()
+    }
+}
+
+impl Default for TempHome {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds a releases listing fixture from raw JSON, parsing it through the
+/// real [`Release`] model so tests fail loudly if the fixture drifts from the
+/// deserialization logic under test.
+pub fn parse_releases(json: &str) -> Vec<Release> {
+    serde_json::from_str(json).expect("invalid releases fixture")
+}
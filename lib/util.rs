@@ -88,10 +88,6 @@ pub async fn write_executable_file(
 /**
     Writes a symlink at the given link path to the given
     target path, and sets the symlink to be executable.
-
-    # Panics
-
-    This function will panic if called on a non-unix system.
 */
 #[cfg(unix)]
 pub async fn write_executable_link(
@@ -124,19 +120,71 @@ pub async fn write_executable_link(
 }
 
 /**
-    Writes a symlink at the given link path to the given
-    target path, and sets the symlink to be executable.
+    Writes an executable launcher at the given link path that dispatches to
+    the given target path.
 
-    # Panics
+    Windows does not allow creating symlinks without Developer Mode or
+    administrator privileges, so instead of a symlink we drop a copy of the
+    Aftman executable named after the tool into the bin directory. When it is
+    invoked, the runner resolves its own file stem from `argv[0]` and re-execs
+    the correct installed tool version, exactly like the unix symlinks do.
 
-    This function will panic if called on a non-unix system.
+    The launcher is written to a temporary file first and then renamed into
+    place so that replacing a stale launcher is atomic.
 */
 #[cfg(not(unix))]
 pub async fn write_executable_link(
-    _link_path: impl AsRef<Path>,
+    link_path: impl AsRef<Path>,
     _target_path: impl AsRef<Path>,
 ) -> AftmanResult<()> {
-    panic!("write_executable_link should only be called on unix systems");
+    use tokio::fs::{copy, rename};
+
+    // A file is only invokable as a bare command on Windows if it ends in
+    // `.exe`, so force that extension regardless of what the caller passed -
+    // the runner still dispatches off the file stem, which `.exe` leaves
+    // untouched.
+    let link_path = link_path.as_ref();
+    let link_path = if link_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+    {
+        link_path.to_path_buf()
+    } else {
+        // Append rather than `with_extension`, which would replace the part
+        // after a dot in a tool name and so change the stem.
+        let mut name = link_path
+            .file_name()
+            .expect("launcher path must have a file name")
+            .to_os_string();
+        name.push(".exe");
+        link_path.with_file_name(name)
+    };
+
+    // The launcher is just a copy of the currently running Aftman binary -
+    // the runner dispatches based on the file stem it was invoked as, so the
+    // target path does not need to be baked into the copy.
+    let current_exe = std::env::current_exe()?;
+
+    // Stage the copy next to the final path so the rename stays on the same
+    // volume, then atomically swap it into place over any existing (stale)
+    // launcher. We append `.tmp` to the full file name rather than replacing
+    // the extension so a tool whose name contains a dot is not truncated.
+    let mut temp_name = link_path
+        .file_name()
+        .expect("launcher path must have a file name")
+        .to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = link_path.with_file_name(temp_name);
+    if let Err(e) = copy(&current_exe, &temp_path).await {
+        error!("Failed to write launcher at {temp_path:?}:\n{e}");
+        return Err(e.into());
+    }
+    if let Err(e) = rename(&temp_path, &link_path).await {
+        error!("Failed to create launcher at {link_path:?}:\n{e}");
+        return Err(e.into());
+    }
+
+    Ok(())
 }
 
 #[cfg(unix)]
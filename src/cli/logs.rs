@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use aftman::storage::Home;
+
+/// How often we re-check the log file for growth when polling.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Tails the background update agent's log.
+#[derive(Debug, Parser)]
+pub struct LogsSubcommand {
+    /// Print the whole log and exit instead of following it.
+    #[clap(long)]
+    no_follow: bool,
+}
+
+impl LogsSubcommand {
+    pub async fn run(&self, home: &Home) -> Result<()> {
+        tail(home, self.no_follow).await
+    }
+}
+
+/// On Linux the agent runs under systemd, so its output lives in the journal;
+/// delegate straight to `journalctl --user` rather than reinventing it.
+#[cfg(target_os = "linux")]
+async fn tail(_home: &Home, no_follow: bool) -> Result<()> {
+    use tokio::process::Command;
+
+    let mut command = Command::new("journalctl");
+    command.args(["--user", "-u", "aftman.service"]);
+    if !no_follow {
+        command.arg("-f");
+    }
+    let status = command
+        .status()
+        .await
+        .context("failed to invoke journalctl")?;
+    anyhow::ensure!(status.success(), "journalctl exited with an error");
+    Ok(())
+}
+
+/// On macOS and Windows the agent writes to a rotating log file in the Aftman
+/// home; follow it with simple file-size polling so we don't take an inotify /
+/// kqueue dependency just for `aftman logs`.
+#[cfg(not(target_os = "linux"))]
+async fn tail(home: &Home, no_follow: bool) -> Result<()> {
+    use tokio::io::{stdout, AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+    let path = home.path().join("aftman.log");
+    let mut file = tokio::fs::File::open(&path)
+        .await
+        .with_context(|| format!("no agent log found at {path:?}"))?;
+
+    let mut out = stdout();
+    let mut offset = 0u64;
+    loop {
+        let len = file.metadata().await?.len();
+        // The log rotates by truncation/replacement; if it shrank, the file
+        // was rotated out from under us, so start reading from the top again.
+        if len < offset {
+            offset = 0;
+            file.seek(std::io::SeekFrom::Start(0)).await?;
+        }
+        if len > offset {
+            let mut buf = Vec::with_capacity((len - offset) as usize);
+            file.read_to_end(&mut buf).await?;
+            out.write_all(&buf).await?;
+            out.flush().await?;
+            offset = len;
+        }
+
+        if no_follow {
+            break;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    Ok(())
+}
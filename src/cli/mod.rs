@@ -6,11 +6,15 @@ use tokio::time::Instant;
 mod add;
 mod install;
 mod list;
+mod logs;
+mod service;
 mod trust;
 
 use self::add::AddSubcommand;
 use self::install::InstallSubcommand;
 use self::list::ListSubcommand;
+use self::logs::LogsSubcommand;
+use self::service::ServiceSubcommand;
 use self::trust::TrustSubcommand;
 
 #[derive(Debug, Parser)]
@@ -66,6 +70,8 @@ pub enum Subcommand {
     List(ListSubcommand),
     Trust(TrustSubcommand),
     Install(InstallSubcommand),
+    Service(ServiceSubcommand),
+    Logs(LogsSubcommand),
 }
 
 impl Subcommand {
@@ -75,6 +81,8 @@ impl Subcommand {
             Self::List(cmd) => cmd.run(home).await,
             Self::Trust(cmd) => cmd.run(home).await,
             Self::Install(cmd) => cmd.run(home).await,
+            Self::Service(cmd) => cmd.run(home).await,
+            Self::Logs(cmd) => cmd.run(home).await,
         }
     }
 }
@@ -3,6 +3,8 @@ use clap::Parser;
 
 use aftman::storage::Home;
 
+mod path;
+
 /// Installs / re-installs Aftman, and updates all tool links.
 #[derive(Debug, Parser)]
 pub struct SelfInstallSubcommand {}
@@ -17,9 +19,13 @@ impl SelfInstallSubcommand {
                 \nYour installation may be corrupted.",
             )?;
 
-        // TODO: Automatically populate the PATH variable
-        let path_was_populated = false;
-        let path_message_lines = if !path_was_populated {
+        let path_was_populated = path::populate_path(storage.bin_dir())
+            .await
+            .context(
+                "Failed to add Aftman to your PATH!\
+                \nYou may need to add it manually.",
+            )?;
+        let path_message_lines = if path_was_populated {
             "\nBinaries for Aftman and tools have been added to your PATH.\
             \nPlease restart your terminal for the changes to take effect."
         } else {
@@ -0,0 +1,215 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+// Markers used to bracket the block we manage in shell profiles. Re-runs
+// look for the opening marker and leave the file untouched if it is found,
+// so self-install stays idempotent no matter how many times it is invoked.
+const BLOCK_START: &str = "# Added by Aftman. Do not edit this block.";
+const BLOCK_END: &str = "# End of Aftman block.";
+
+/**
+    Adds the given tool binary directory to the user's `PATH`.
+
+    Returns `true` if the directory was newly added, or `false` if it was
+    already present and nothing had to change. The operation is idempotent -
+    running it repeatedly will only ever add the entry once.
+*/
+pub(super) async fn populate_path(bin_dir: &Path) -> Result<bool> {
+    populate_path_inner(bin_dir).await
+}
+
+#[cfg(unix)]
+async fn populate_path_inner(bin_dir: &Path) -> Result<bool> {
+    use tokio::fs::{read_to_string, OpenOptions};
+    use tokio::io::AsyncWriteExt;
+
+    let shell = Shell::detect();
+    let profile = shell.profile_path()?;
+
+    // The exported line itself, plus the guard markers around it. Keeping the
+    // markers on their own lines means a future version can find and rewrite
+    // the block without disturbing anything else the user put in their profile.
+    let export_line = shell.export_line(bin_dir);
+    let block = format!("{BLOCK_START}\n{export_line}\n{BLOCK_END}\n");
+
+    if let Ok(contents) = read_to_string(&profile).await {
+        if contents.contains(BLOCK_START) {
+            return Ok(false);
+        }
+    }
+
+    // Make sure the parent directory exists - some shells (notably fish) keep
+    // their config several directories deep and the user may not have it yet.
+    if let Some(parent) = profile.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&profile)
+        .await?;
+    // Prefix a newline so we never glue our block onto a line the user was
+    // in the middle of, regardless of whether the file ended with one.
+    file.write_all(format!("\n{block}").as_bytes()).await?;
+    file.flush().await?;
+
+    Ok(true)
+}
+
+/// The login shell we detected, used to pick the right profile file and the
+/// correct syntax for extending `PATH`.
+#[cfg(unix)]
+enum Shell {
+    Zsh,
+    Bash,
+    Fish,
+    /// An unknown or POSIX shell - fall back to `.profile` and `sh` syntax.
+    Other,
+}
+
+#[cfg(unix)]
+impl Shell {
+    /// Detects the login shell from `$SHELL`, falling back to the `passwd`
+    /// entry for the current user when the environment variable is missing.
+    fn detect() -> Self {
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| login_shell_from_passwd());
+        match shell.rsplit('/').next().unwrap_or_default() {
+            "zsh" => Self::Zsh,
+            "bash" => Self::Bash,
+            "fish" => Self::Fish,
+            _ => Self::Other,
+        }
+    }
+
+    /// Returns the profile file this shell reads on login.
+    fn profile_path(&self) -> Result<std::path::PathBuf> {
+        let home = home_dir()?;
+        Ok(match self {
+            Self::Zsh => home.join(".zshrc"),
+            // Prefer `.bashrc` when it already exists, otherwise fall back to
+            // `.bash_profile` which login shells read on macOS.
+            Self::Bash => {
+                let bashrc = home.join(".bashrc");
+                if bashrc.exists() {
+                    bashrc
+                } else {
+                    home.join(".bash_profile")
+                }
+            }
+            Self::Fish => home.join(".config").join("fish").join("config.fish"),
+            Self::Other => home.join(".profile"),
+        })
+    }
+
+    /// Returns the line that prepends `bin_dir` to `PATH` in this shell.
+    fn export_line(&self, bin_dir: &Path) -> String {
+        let dir = bin_dir.display();
+        match self {
+            Self::Fish => format!("set -gx PATH \"{dir}\" $PATH"),
+            _ => format!("export PATH=\"{dir}:$PATH\""),
+        }
+    }
+}
+
+/// Reads the current user's login shell out of `/etc/passwd`, returning an
+/// empty string when it cannot be determined (which falls through to the
+/// POSIX `.profile` default).
+#[cfg(unix)]
+fn login_shell_from_passwd() -> String {
+    use std::os::unix::ffi::OsStringExt;
+
+    let uid = unsafe { libc::getuid() };
+    let Ok(passwd) = std::fs::read(std::ffi::OsString::from_vec(b"/etc/passwd".to_vec())) else {
+        return String::new();
+    };
+    let passwd = String::from_utf8_lossy(&passwd);
+    for line in passwd.lines() {
+        // name:passwd:uid:gid:gecos:dir:shell
+        let mut fields = line.split(':');
+        let Some(_name) = fields.next() else { continue };
+        let _ = fields.next();
+        if fields.next().and_then(|u| u.parse::<u32>().ok()) != Some(uid) {
+            continue;
+        }
+        if let Some(shell) = fields.nth(3) {
+            return shell.to_string();
+        }
+    }
+    String::new()
+}
+
+#[cfg(unix)]
+fn home_dir() -> Result<std::path::PathBuf> {
+    std::env::var_os("HOME")
+        .map(std::path::PathBuf::from)
+        .ok_or_else(|| anyhow::anyhow!("could not determine home directory from $HOME"))
+}
+
+#[cfg(windows)]
+async fn populate_path_inner(bin_dir: &Path) -> Result<bool> {
+    // Registry writes and the settings broadcast are synchronous Win32 calls,
+    // so do them on a blocking thread to avoid stalling the async runtime.
+    let bin_dir = bin_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || populate_path_windows(&bin_dir)).await?
+}
+
+#[cfg(windows)]
+fn populate_path_windows(bin_dir: &Path) -> Result<bool> {
+    use winreg::enums::{HKEY_CURRENT_USER, KEY_READ, KEY_WRITE, REG_EXPAND_SZ};
+    use winreg::RegKey;
+
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let environment = hkcu.open_subkey_with_flags("Environment", KEY_READ | KEY_WRITE)?;
+
+    // The user PATH may be absent entirely on a fresh profile.
+    let current: String = environment.get_value("Path").unwrap_or_default();
+
+    let bin_dir = bin_dir.to_string_lossy();
+    let already_present = current
+        .split(';')
+        .any(|entry| entry.eq_ignore_ascii_case(bin_dir.trim_end_matches('\\')));
+    if already_present {
+        return Ok(false);
+    }
+
+    let new_path = if current.is_empty() {
+        bin_dir.to_string()
+    } else {
+        format!("{bin_dir};{current}")
+    };
+
+    // Always write back as REG_EXPAND_SZ so `%USERPROFILE%`-style entries the
+    // user already had keep expanding correctly.
+    environment.set_value_expand("Path", &new_path, REG_EXPAND_SZ)?;
+
+    broadcast_environment_change();
+
+    Ok(true)
+}
+
+/// Broadcasts `WM_SETTINGCHANGE` so that already-running processes (Explorer,
+/// open terminals, ...) reload the environment without a logout.
+#[cfg(windows)]
+fn broadcast_environment_change() {
+    use std::ptr::null_mut;
+    use winapi::shared::minwindef::LPARAM;
+    use winapi::um::winuser::{
+        SendMessageTimeoutW, HWND_BROADCAST, SMTO_ABORTIFHUNG, WM_SETTINGCHANGE,
+    };
+
+    let environment: Vec<u16> = "Environment\0".encode_utf16().collect();
+    let mut result = 0;
+    unsafe {
+        SendMessageTimeoutW(
+            HWND_BROADCAST,
+            WM_SETTINGCHANGE,
+            0,
+            environment.as_ptr() as LPARAM,
+            SMTO_ABORTIFHUNG,
+            5000,
+            &mut result,
+        );
+    }
+}
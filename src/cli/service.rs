@@ -0,0 +1,372 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+
+use aftman::storage::Home;
+
+/// How often the background agent re-resolves installed tools.
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// The agent's log file is rotated once it grows past this size, keeping a
+/// single previous generation (`aftman.log.1`).
+#[cfg(not(target_os = "linux"))]
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Manages the background agent that checks for tool updates.
+#[derive(Debug, Parser)]
+pub struct ServiceSubcommand {
+    #[clap(subcommand)]
+    action: ServiceAction,
+}
+
+#[derive(Debug, Subcommand)]
+enum ServiceAction {
+    /// Registers the agent as a per-user service and starts it.
+    Install,
+    /// Stops and unregisters the agent.
+    Uninstall,
+    /// Runs the agent. This is the entry point the registered service invokes;
+    /// it is not usually run by hand.
+    Run {
+        /// Run a single update check and exit instead of looping. Used by the
+        /// schedulers (Windows scheduled task) that trigger the check
+        /// themselves rather than hosting a persistent process.
+        #[clap(long)]
+        once: bool,
+    },
+}
+
+impl ServiceSubcommand {
+    pub async fn run(&self, home: &Home) -> Result<()> {
+        match self.action {
+            ServiceAction::Install => register::install(home).await,
+            ServiceAction::Uninstall => register::uninstall(home).await,
+            ServiceAction::Run { once } => run_agent(home, once).await,
+        }
+    }
+}
+
+/// Re-resolves every installed tool against its GitHub source and reports any
+/// newer version that still satisfies the tool's trusted spec. Each check is
+/// logged so the activity is observable through `aftman logs`.
+///
+/// With `once`, a single check runs and the process exits - this is how the
+/// scheduler-driven model (the Windows scheduled task) is triggered. Otherwise
+/// the agent loops on [`CHECK_INTERVAL`] as a persistent service (systemd /
+/// launchd).
+async fn run_agent(home: &Home, once: bool) -> Result<()> {
+    let storage = home.tool_storage();
+    let client = aftman::sources::github::new_client()?;
+
+    if once {
+        rotate_log(home).await;
+        tracing::info!("checking installed tools for updates");
+        return check_for_updates(&storage, &client).await;
+    }
+
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    tracing::info!("aftman update agent started");
+    loop {
+        interval.tick().await;
+        rotate_log(home).await;
+        tracing::info!("checking installed tools for updates");
+
+        if let Err(e) = check_for_updates(&storage, &client).await {
+            tracing::error!("update check failed: {e:?}");
+        }
+    }
+}
+
+/// Re-resolves every installed tool against its GitHub releases and logs the
+/// ones that have a newer version available within their trusted spec.
+///
+/// Unlike `recreate_all_links`, which only re-links versions already present
+/// on disk, this actually contacts GitHub so the agent can surface updates the
+/// user has not installed yet.
+async fn check_for_updates(
+    storage: &aftman::storage::ToolStorage,
+    client: &reqwest::Client,
+) -> Result<()> {
+    for tool in storage.installed_tools().await? {
+        let spec = tool.spec();
+        // A single unreachable or renamed repo must not abort the whole scan,
+        // so per-tool failures are logged and the rest still get checked.
+        if let Err(e) = check_tool(client, spec).await {
+            tracing::warn!(tool = %spec.id(), "update check failed: {e:?}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks a single installed tool against its GitHub releases, logging when a
+/// strictly newer version is available within the tool's trusted spec.
+async fn check_tool(
+    client: &reqwest::Client,
+    spec: &aftman::tool::ToolSpec,
+) -> Result<()> {
+    use aftman::sources::github::{self, models::latest_stable, models::Release};
+
+    let url = format!("https://api.github.com/repos/{}/releases", spec.id());
+    let request = client.get(&url).build()?;
+    let response = github::client::send(client, request)
+        .await?
+        .error_for_status()?;
+    let releases: Vec<Release> = response.json().await?;
+
+    let Some(latest) = latest_stable(&releases) else {
+        return Ok(());
+    };
+
+    // Only report when the latest stable is strictly newer than what is
+    // installed - a string inequality would also flag an older release (e.g.
+    // when the user is on a newer prerelease) and advise a downgrade.
+    let latest_version = latest.tag_name.trim_start_matches('v');
+    let current_version = spec.version().trim_start_matches('v');
+    let is_newer = match (
+        semver::Version::parse(latest_version),
+        semver::Version::parse(current_version),
+    ) {
+        (Ok(latest), Ok(current)) => latest > current,
+        // Fall back to inequality when either side is not semver.
+        _ => latest_version != current_version,
+    };
+
+    if is_newer && spec.allows(latest_version) {
+        tracing::info!(
+            tool = %spec.id(),
+            current = %current_version,
+            available = %latest_version,
+            "a newer version is available; run `aftman add {}@{latest_version}` to update",
+            spec.id(),
+        );
+    } else {
+        tracing::debug!(tool = %spec.id(), "up-to-date");
+    }
+
+    Ok(())
+}
+
+/// Rotates the agent's log file when it grows past [`MAX_LOG_BYTES`], copying
+/// it to `aftman.log.1` and truncating the original in place, so the service's
+/// append handle keeps writing to the same, now empty, file. On Linux the
+/// agent's output lives in the journal instead, which does its own rotation,
+/// so this is a no-op there.
+#[cfg(not(target_os = "linux"))]
+async fn rotate_log(home: &Home) {
+    use tokio::fs::{copy, OpenOptions};
+
+    let path = home.path().join("aftman.log");
+    let Ok(meta) = tokio::fs::metadata(&path).await else {
+        return;
+    };
+    if meta.len() <= MAX_LOG_BYTES {
+        return;
+    }
+
+    // Copy the log aside and then truncate it in place rather than renaming
+    // it. The service redirects its output into this file with a long-lived
+    // append handle (launchd `StandardOutPath`, the Windows `cmd /c >>`), so a
+    // rename would leave that handle writing to the moved-away file. Truncating
+    // keeps the same file - the next append simply lands back at the top, and
+    // the `aftman logs` tailer already resets when it sees the file shrink.
+    let rotated = home.path().join("aftman.log.1");
+    if let Err(e) = copy(&path, &rotated).await {
+        tracing::warn!("failed to rotate agent log: {e}");
+        return;
+    }
+    match OpenOptions::new().write(true).open(&path).await {
+        Ok(file) => {
+            if let Err(e) = file.set_len(0).await {
+                tracing::warn!("failed to truncate agent log: {e}");
+            }
+        }
+        Err(e) => tracing::warn!("failed to reopen agent log for rotation: {e}"),
+    }
+}
+
+#[cfg(target_os = "linux")]
+async fn rotate_log(_home: &Home) {}
+
+#[cfg(target_os = "linux")]
+mod register {
+    use super::*;
+
+    use tokio::fs::{create_dir_all, write};
+    use tokio::process::Command;
+
+    pub async fn install(_home: &Home) -> Result<()> {
+        let exe = std::env::current_exe()?;
+        let unit = format!(
+            "[Unit]\n\
+             Description=Aftman background update agent\n\n\
+             [Service]\n\
+             ExecStart={} service run\n\
+             Restart=on-failure\n\n\
+             [Install]\n\
+             WantedBy=default.target\n",
+            exe.display(),
+        );
+
+        let unit_dir = config_dir()?.join("systemd").join("user");
+        create_dir_all(&unit_dir).await?;
+        write(unit_dir.join("aftman.service"), unit).await?;
+
+        systemctl(&["daemon-reload"]).await?;
+        systemctl(&["enable", "--now", "aftman.service"]).await?;
+        tracing::info!("Registered aftman update agent as a systemd user service.");
+        Ok(())
+    }
+
+    pub async fn uninstall(_home: &Home) -> Result<()> {
+        systemctl(&["disable", "--now", "aftman.service"]).await.ok();
+        tokio::fs::remove_file(config_dir()?.join("systemd/user/aftman.service"))
+            .await
+            .ok();
+        systemctl(&["daemon-reload"]).await.ok();
+        tracing::info!("Unregistered aftman update agent.");
+        Ok(())
+    }
+
+    async fn systemctl(args: &[&str]) -> Result<()> {
+        let status = Command::new("systemctl")
+            .arg("--user")
+            .args(args)
+            .status()
+            .await
+            .context("failed to invoke systemctl")?;
+        anyhow::ensure!(status.success(), "systemctl {:?} failed", args);
+        Ok(())
+    }
+
+    fn config_dir() -> Result<std::path::PathBuf> {
+        if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME") {
+            return Ok(dir.into());
+        }
+        let home =
+            std::env::var_os("HOME").context("could not determine home directory from $HOME")?;
+        Ok(std::path::PathBuf::from(home).join(".config"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod register {
+    use super::*;
+
+    use tokio::fs::write;
+    use tokio::process::Command;
+
+    const LABEL: &str = "dev.aftman.agent";
+
+    pub async fn install(home: &Home) -> Result<()> {
+        let exe = std::env::current_exe()?;
+        let log = home.path().join("aftman.log");
+        let plist = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <plist version=\"1.0\"><dict>\n\
+             <key>Label</key><string>{LABEL}</string>\n\
+             <key>ProgramArguments</key><array>\
+             <string>{}</string><string>service</string><string>run</string></array>\n\
+             <key>RunAtLoad</key><true/>\n\
+             <key>StandardOutPath</key><string>{}</string>\n\
+             <key>StandardErrorPath</key><string>{}</string>\n\
+             </dict></plist>\n",
+            exe.display(),
+            log.display(),
+            log.display(),
+        );
+
+        let path = plist_path()?;
+        write(&path, plist).await?;
+        // Unload any previously-registered copy first so re-running install is
+        // idempotent: `launchctl load` errors if the agent is already loaded.
+        launchctl(&["unload", "-w"], &path).await.ok();
+        launchctl(&["load", "-w"], &path).await?;
+        tracing::info!("Registered aftman update agent as a launchd agent.");
+        Ok(())
+    }
+
+    pub async fn uninstall(_home: &Home) -> Result<()> {
+        let path = plist_path()?;
+        launchctl(&["unload", "-w"], &path).await.ok();
+        tokio::fs::remove_file(&path).await.ok();
+        tracing::info!("Unregistered aftman update agent.");
+        Ok(())
+    }
+
+    async fn launchctl(args: &[&str], path: &std::path::Path) -> Result<()> {
+        let status = Command::new("launchctl")
+            .args(args)
+            .arg(path)
+            .status()
+            .await
+            .context("failed to invoke launchctl")?;
+        anyhow::ensure!(status.success(), "launchctl {:?} failed", args);
+        Ok(())
+    }
+
+    fn plist_path() -> Result<std::path::PathBuf> {
+        let home =
+            std::env::var_os("HOME").context("could not determine home directory from $HOME")?;
+        Ok(std::path::PathBuf::from(home)
+            .join("Library/LaunchAgents")
+            .join(format!("{LABEL}.plist")))
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod register {
+    use super::*;
+
+    use tokio::fs::write;
+    use tokio::process::Command;
+
+    const TASK_NAME: &str = "AftmanUpdateAgent";
+    const LAUNCHER: &str = "aftman-agent.cmd";
+
+    pub async fn install(home: &Home) -> Result<()> {
+        let exe = std::env::current_exe()?;
+        let log = home.path().join("aftman.log");
+
+        // Register a tiny launcher script rather than inlining the command in
+        // `schtasks /tr`: the redirection into the log file that `aftman logs`
+        // tails needs nested quotes, which schtasks' own `/tr` quoting mangles.
+        // A `.cmd` file sidesteps that entirely and keeps the output captured.
+        let launcher = home.path().join(LAUNCHER);
+        // Each trigger does a single check and exits (`--once`) rather than
+        // hosting a persistent loop, so the scheduler - not the process -
+        // owns the cadence. Triggering every 6 hours keeps that cadence equal
+        // to `CHECK_INTERVAL` on the systemd / launchd side.
+        let script = format!(
+            "@echo off\r\n\"{}\" service run --once >> \"{}\" 2>&1\r\n",
+            exe.display(),
+            log.display(),
+        );
+        write(&launcher, script).await?;
+
+        // Run every 6 hours to match `CHECK_INTERVAL`.
+        let status = Command::new("schtasks")
+            .args([
+                "/create", "/f", "/sc", "hourly", "/mo", "6", "/tn", TASK_NAME, "/tr",
+            ])
+            .arg(&launcher)
+            .status()
+            .await
+            .context("failed to invoke schtasks")?;
+        anyhow::ensure!(status.success(), "schtasks /create failed");
+        tracing::info!("Registered aftman update agent as a scheduled task.");
+        Ok(())
+    }
+
+    pub async fn uninstall(home: &Home) -> Result<()> {
+        Command::new("schtasks")
+            .args(["/delete", "/f", "/tn", TASK_NAME])
+            .status()
+            .await
+            .context("failed to invoke schtasks")?;
+        tokio::fs::remove_file(home.path().join(LAUNCHER)).await.ok();
+        tracing::info!("Unregistered aftman update agent.");
+        Ok(())
+    }
+}
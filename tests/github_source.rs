@@ -0,0 +1,162 @@
+//! End-to-end tests for the GitHub source against a mock release server.
+//!
+//! These drive `Release`/`Asset` deserialization, asset selection, streaming
+//! downloads, and the not-found path against deterministic fixtures so the
+//! logic is regression-tested without any network access.
+
+#![cfg(feature = "test-support")]
+
+use aftman::sources::github::models::latest_stable;
+use aftman::sources::github::test_support::{parse_releases, MockData, MockGitHub, TempHome};
+use aftman::sources::github::{Pipeline, PipelineContext, Step};
+
+/// A listing with a stable release carrying one asset per platform, plus a
+/// newer prerelease that stable-release selection must be able to skip.
+fn releases_fixture(base_url: &str) -> String {
+    format!(
+        r#"[
+            {{
+                "tag_name": "v1.1.0-rc.1",
+                "prerelease": true,
+                "assets": [
+                    {{ "id": 10, "name": "tool-1.1.0-rc.1-linux-x86_64.zip", "url": "{base_url}/download/tool-1.1.0-rc.1-linux-x86_64.zip" }}
+                ]
+            }},
+            {{
+                "tag_name": "v1.0.0",
+                "prerelease": false,
+                "assets": [
+                    {{ "id": 1, "name": "tool-1.0.0-linux-x86_64.zip", "url": "{base_url}/download/tool-1.0.0-linux-x86_64.zip" }},
+                    {{ "id": 2, "name": "tool-1.0.0-macos-aarch64.zip", "url": "{base_url}/download/tool-1.0.0-macos-aarch64.zip" }},
+                    {{ "id": 3, "name": "tool-1.0.0-windows-x86_64.zip", "url": "{base_url}/download/tool-1.0.0-windows-x86_64.zip" }}
+                ]
+            }}
+        ]"#
+    )
+}
+
+#[tokio::test]
+async fn filters_prereleases_and_matches_platform() {
+    let releases = parse_releases(&releases_fixture("http://example.invalid"));
+
+    // Prerelease filtering uses the source's own selection logic.
+    let stable = latest_stable(&releases).expect("a stable release");
+    assert_eq!(stable.tag_name, "v1.0.0");
+    assert_eq!(stable.assets.len(), 3);
+
+    // Multi-platform asset name matching, again via the real resolver.
+    let linux = stable.asset_for_platform("linux", "x86_64").unwrap();
+    assert_eq!(linux.id, 1);
+    let macos = stable.asset_for_platform("macos", "aarch64").unwrap();
+    assert_eq!(macos.id, 2);
+    assert!(stable.asset_for_platform("freebsd", "x86_64").is_none());
+}
+
+#[tokio::test]
+async fn downloads_asset_bytes_from_mock_server() {
+    let server = MockGitHub::start(MockData {
+        releases_json: String::new(),
+        assets: [(
+            "tool-1.0.0-linux-x86_64.zip".to_string(),
+            b"fake asset bytes".to_vec(),
+        )]
+        .into_iter()
+        .collect(),
+    })
+    .await;
+
+    let releases = parse_releases(&releases_fixture(&server.base_url()));
+    let stable = latest_stable(&releases).unwrap();
+    let asset = stable.asset_for_platform("linux", "x86_64").unwrap();
+
+    let home = TempHome::new();
+    let dest = home.path().join("tool.zip");
+
+    let client = aftman::sources::github::new_client().unwrap();
+    asset.download_to(&client, &dest).await.unwrap();
+
+    let written = std::fs::read(&dest).unwrap();
+    assert_eq!(written, b"fake asset bytes");
+}
+
+/// A single stable release carrying one bare-binary asset for the host, used
+/// to drive the install pipeline all the way to the written binary and links.
+fn single_binary_fixture(base_url: &str) -> String {
+    format!(
+        r#"[
+            {{
+                "tag_name": "v1.0.0",
+                "prerelease": false,
+                "assets": [
+                    {{ "id": 1, "name": "tool-1.0.0-linux-x86_64", "url": "{base_url}/download/tool-1.0.0-linux-x86_64" }}
+                ]
+            }}
+        ]"#
+    )
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn install_pipeline_writes_binary_and_alias_links() {
+    let server = MockGitHub::start(MockData {
+        releases_json: String::new(),
+        assets: [(
+            "tool-1.0.0-linux-x86_64".to_string(),
+            b"#!/bin/sh\necho tool\n".to_vec(),
+        )]
+        .into_iter()
+        .collect(),
+    })
+    .await;
+
+    let releases = parse_releases(&single_binary_fixture(&server.base_url()));
+    let release = latest_stable(&releases).unwrap().clone();
+
+    let home = TempHome::new();
+    let bin_dir = home.path().join("bin");
+    let staging = home.path().join("staging");
+    std::fs::create_dir_all(&bin_dir).unwrap();
+    std::fs::create_dir_all(&staging).unwrap();
+    let destination = bin_dir.join("tool");
+
+    // Drive the real install pipeline end to end against the mock server.
+    let client = aftman::sources::github::new_client().unwrap();
+    let mut cx = PipelineContext::new(client, &staging);
+    let pipeline = Pipeline::new(vec![
+        Step::ResolveRelease {
+            release,
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+        },
+        Step::DownloadAsset,
+        Step::Extract,
+        Step::WriteExecutable {
+            destination: destination.clone(),
+        },
+        Step::LinkAliases {
+            aliases: vec!["tool-alias".to_string()],
+        },
+    ]);
+    pipeline.run(&mut cx).await.unwrap();
+
+    // The binary is written into the tool bin dir...
+    assert_eq!(std::fs::read(&destination).unwrap(), b"#!/bin/sh\necho tool\n");
+    // ...and the alias is a link sitting next to it.
+    let alias = bin_dir.join("tool-alias");
+    let link_meta = std::fs::symlink_metadata(&alias).expect("alias link should exist");
+    assert!(link_meta.file_type().is_symlink());
+}
+
+#[tokio::test]
+async fn missing_asset_returns_not_found() {
+    let server = MockGitHub::start(MockData::default()).await;
+
+    let client = aftman::sources::github::new_client().unwrap();
+    let response = client
+        .get(format!("{}/download/does-not-exist.zip", server.base_url()))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+}